@@ -0,0 +1,43 @@
+/// How a docstring should be rendered for the target language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// `/// ...` Rust doc comments.
+    TripleSlash,
+    /// `// ...` plain comments.
+    DoubleSlash,
+    /// `/** ... */` JSDoc block comments.
+    JsDoc,
+}
+
+/// Renders `docs` as a comment block in the given `style`, indented by
+/// `indent_level` spaces. Returns `None` when there are no docs to emit.
+pub fn format_docstring(
+    docs: Option<&str>,
+    style: CommentStyle,
+    indent_level: usize,
+) -> Option<String> {
+    let docs = docs?;
+    let indent = " ".repeat(indent_level);
+
+    let mut out = String::new();
+    match style {
+        CommentStyle::TripleSlash | CommentStyle::DoubleSlash => {
+            let prefix = match style {
+                CommentStyle::TripleSlash => "///",
+                _ => "//",
+            };
+            for line in docs.lines() {
+                out.push_str(&format!("{}{} {}\n", indent, prefix, line));
+            }
+        }
+        CommentStyle::JsDoc => {
+            out.push_str(&format!("{}/**\n", indent));
+            for line in docs.lines() {
+                out.push_str(&format!("{} * {}\n", indent, line));
+            }
+            out.push_str(&format!("{} */\n", indent));
+        }
+    }
+
+    Some(out)
+}
@@ -0,0 +1,32 @@
+pub mod docs;
+pub mod protobuf;
+pub mod rust;
+pub mod sql;
+pub mod typescript;
+
+use crate::definitions::Declarations;
+use anyhow::Result;
+
+/// Implemented by each output backend that projects a [`Declarations`] IR
+/// onto a target language or schema format.
+pub trait Codegen {
+    fn gen_declarations(declarations: &Declarations) -> Result<String>;
+}
+
+/// Converts a `PascalCase` declaration name into a `snake_case` identifier,
+/// shared by any backend that needs to derive an identifier (Rust `visit_*`
+/// function, SQL table/column name, ...) from a declaration name.
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
@@ -0,0 +1,374 @@
+use super::Codegen;
+use crate::definitions::*;
+use anyhow::{anyhow, Result};
+
+/// Projects the [`Declarations`] IR onto a proto3 `.proto` schema, so gull
+/// can serve as a single source of truth for both Rust structs and wire
+/// schemas.
+///
+/// Field numbers matter for wire compatibility, so this backend honours
+/// [`StructFieldConfig::ProtoFieldNumber`] / [`EnumVariantConfig::ProtoFieldNumber`]
+/// pins and falls back to auto-assignment starting at 1 (at 0 for the values
+/// of a plain proto `enum`), skipping any number that's already pinned.
+pub struct ProtobufCodegen;
+
+impl Codegen for ProtobufCodegen {
+    fn gen_declarations(declarations: &Declarations) -> Result<String> {
+        let proto = ProtobufCodegen;
+
+        let mut result = String::from("syntax = \"proto3\";\n");
+
+        for declaration in &declarations.declarations {
+            if let Some(block) = proto.gen_declaration(declaration)? {
+                result.push('\n');
+                result.push_str(&block);
+                result.push('\n');
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl ProtobufCodegen {
+    fn gen_declaration(&self, declaration: &TypeDeclaration) -> Result<Option<String>> {
+        match &declaration.value {
+            DeclarationValue::TStruct(s) => {
+                Ok(Some(self.gen_message(declaration.name, s)?))
+            }
+            DeclarationValue::TEnum(e) => Ok(Some(self.gen_enum(declaration.name, e)?)),
+            DeclarationValue::TPrimitive(_)
+            | DeclarationValue::TMap(_)
+            | DeclarationValue::TTuple(_)
+            | DeclarationValue::Docs => Ok(None),
+        }
+    }
+
+    fn gen_message(&self, name: &str, s: &TStruct) -> Result<String> {
+        let numbers = assign_field_numbers(
+            s.fields.iter().map(|f| {
+                (
+                    f.name,
+                    f.config.iter().find_map(|c| match c {
+                        StructFieldConfig::ProtoFieldNumber(n) => Some(*n),
+                        _ => None,
+                    }),
+                )
+            }),
+            name,
+        )?;
+
+        let mut fields = String::new();
+        for (field, number) in s.fields.iter().zip(numbers) {
+            let (modifier, ty) = self.gen_field_type(&field.field_type)?;
+            fields.push_str(&format!(
+                "  {}{} {} = {};\n",
+                modifier, ty, field.name, number
+            ));
+        }
+
+        Ok(format!("message {} {{\n{}}}", name, fields))
+    }
+
+    fn gen_enum(&self, name: &str, e: &TEnum) -> Result<String> {
+        let all_empty = e
+            .variants
+            .iter()
+            .all(|v| matches!(v.variant_type, EnumVariantType::Empty));
+
+        if all_empty {
+            let numbers = assign_field_numbers_from_zero(
+                e.variants.iter().map(|v| {
+                    (
+                        v.name,
+                        v.config
+                            .iter()
+                            .map(|c| match c {
+                                EnumVariantConfig::ProtoFieldNumber(n) => *n,
+                            })
+                            .next(),
+                    )
+                }),
+                name,
+            )?;
+
+            if !numbers.contains(&0) {
+                return Err(anyhow!(
+                    "proto3 enum `{}` has no variant numbered 0 (the first value of a proto3 enum must be 0); pin one with `EnumVariantConfig::ProtoFieldNumber(0)`",
+                    name
+                ));
+            }
+
+            // Emit in number order so the value numbered 0 is listed first.
+            let mut numbered: Vec<(&EnumVariant, u32)> = e.variants.iter().zip(numbers).collect();
+            numbered.sort_by_key(|(_, number)| *number);
+
+            let mut variants = String::new();
+            for (variant, number) in numbered {
+                variants.push_str(&format!("  {} = {};\n", variant.name, number));
+            }
+
+            return Ok(format!("enum {} {{\n{}}}", name, variants));
+        }
+
+        let numbers = assign_field_numbers(
+            e.variants.iter().map(|v| {
+                (
+                    v.name,
+                    v.config
+                        .iter()
+                        .map(|c| match c {
+                            EnumVariantConfig::ProtoFieldNumber(n) => *n,
+                        })
+                        .next(),
+                )
+            }),
+            name,
+        )?;
+
+        let mut variants = String::new();
+        for (variant, number) in e.variants.iter().zip(numbers) {
+            let (_, ty) = match &variant.variant_type {
+                EnumVariantType::Empty => ("".to_string(), "bool".to_string()),
+                EnumVariantType::Tuple(t) => {
+                    if t.items.len() != 1 {
+                        return Err(anyhow!(
+                            "protobuf oneof field `{}` needs a wrapper message for tuples that don't have exactly one item",
+                            variant.name
+                        ));
+                    }
+                    ("".to_string(), self.gen_tuple_item_type(&t.items[0]))
+                }
+                EnumVariantType::Struct(_) => {
+                    return Err(anyhow!(
+                        "protobuf oneof field `{}` needs a wrapper message for struct variants",
+                        variant.name
+                    ))
+                }
+            };
+
+            variants.push_str(&format!("    {} {} = {};\n", ty, variant.name, number));
+        }
+
+        Ok(format!(
+            "message {} {{\n  oneof value {{\n{}  }}\n}}",
+            name, variants
+        ))
+    }
+
+    fn gen_field_type(&self, field_type: &StructFieldType) -> Result<(String, String)> {
+        Ok(match field_type {
+            StructFieldType::Reference(r) => ("".into(), r.name.to_string()),
+            StructFieldType::TPrimitive(p) => ("".into(), self.gen_primitive_type(p).to_string()),
+            StructFieldType::TOption(o) => ("optional ".into(), self.gen_option_type(o)?),
+            StructFieldType::TVec(v) => ("repeated ".into(), self.gen_vec_type(v)),
+            StructFieldType::TTuple(_) => {
+                return Err(anyhow!(
+                    "protobuf has no tuple type; wrap it in a message"
+                ))
+            }
+            StructFieldType::TMap(m) => {
+                let value = match &m.value {
+                    TMapValue::TPrimitive(p) => self.gen_primitive_type(p).to_string(),
+                    TMapValue::Reference(r) => r.name.to_string(),
+                };
+                (
+                    "".into(),
+                    format!("map<{}, {}>", self.gen_primitive_type(&m.key), value),
+                )
+            }
+        })
+    }
+
+    fn gen_option_type(&self, o: &TOption) -> Result<String> {
+        Ok(match o {
+            TOption::Reference(r) => r.name.to_string(),
+            TOption::TPrimitive(p) => self.gen_primitive_type(p).to_string(),
+            TOption::TMap(_) | TOption::TVec(_) | TOption::TSet(_) => {
+                return Err(anyhow!(
+                    "protobuf `optional` cannot wrap a repeated or map field"
+                ))
+            }
+        })
+    }
+
+    fn gen_vec_type(&self, v: &TVec) -> String {
+        match v {
+            TVec::TPrimitive(p) => self.gen_primitive_type(p).to_string(),
+            TVec::Reference(r) => r.name.to_string(),
+        }
+    }
+
+    fn gen_tuple_item_type(&self, item: &TupleItem) -> String {
+        match item {
+            TupleItem::Reference(r) => r.name.to_string(),
+            TupleItem::TPrimitive(p) => self.gen_primitive_type(p).to_string(),
+        }
+    }
+
+    fn gen_primitive_type(&self, ty: &TPrimitive) -> &'static str {
+        match ty {
+            TPrimitive::String => "string",
+            TPrimitive::Tbool => "bool",
+            TPrimitive::Ti64 => "int64",
+            TPrimitive::Tf64 => "double",
+        }
+    }
+}
+
+/// Assigns a tag number to each item in order, honouring any pinned number
+/// and auto-assigning the rest starting at 1 while skipping pinned numbers.
+/// `0` may not be pinned here: proto3 reserves the number 0 for the first
+/// value of an enum, never a message or oneof field.
+fn assign_field_numbers<'a>(
+    pins: impl Iterator<Item = (&'a str, Option<u32>)>,
+    context: &str,
+) -> Result<Vec<u32>> {
+    assign_field_numbers_from(pins, 1, context)
+}
+
+/// Like [`assign_field_numbers`], but starting at (and allowing a pin of) 0
+/// — for the zero-variant of an all-empty proto3 enum.
+fn assign_field_numbers_from_zero<'a>(
+    pins: impl Iterator<Item = (&'a str, Option<u32>)>,
+    context: &str,
+) -> Result<Vec<u32>> {
+    assign_field_numbers_from(pins, 0, context)
+}
+
+fn assign_field_numbers_from<'a>(
+    pins: impl Iterator<Item = (&'a str, Option<u32>)>,
+    start: u32,
+    context: &str,
+) -> Result<Vec<u32>> {
+    let pins: Vec<(&str, Option<u32>)> = pins.collect();
+
+    if start != 0 {
+        if let Some((name, _)) = pins.iter().find(|(_, p)| *p == Some(0)) {
+            return Err(anyhow!(
+                "`{}` in `{}` is pinned to field number 0, but proto3 reserves 0 for the first value of an enum",
+                name,
+                context
+            ));
+        }
+    }
+
+    let mut taken: std::collections::HashMap<u32, &str> = std::collections::HashMap::new();
+    for (name, pin) in &pins {
+        if let Some(n) = pin {
+            if let Some(other) = taken.insert(*n, name) {
+                return Err(anyhow!(
+                    "`{}` and `{}` in `{}` are both pinned to field number {}",
+                    other,
+                    name,
+                    context,
+                    n
+                ));
+            }
+        }
+    }
+
+    let mut next = start;
+    Ok(pins
+        .into_iter()
+        .map(|(_, pin)| match pin {
+            Some(n) => n,
+            None => {
+                while taken.contains_key(&next) {
+                    next += 1;
+                }
+                let assigned = next;
+                next += 1;
+                assigned
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_empty_enum_without_a_zero_variant_is_a_reported_error() {
+        let decls = Declarations {
+            declarations: vec![TypeDeclaration {
+                name: "Color",
+                config: vec![],
+                docs: None,
+                value: DeclarationValue::TEnum(TEnum {
+                    variants: vec![
+                        EnumVariant {
+                            name: "Red",
+                            config: vec![EnumVariantConfig::ProtoFieldNumber(5)],
+                            docs: None,
+                            variant_type: EnumVariantType::Empty,
+                        },
+                        EnumVariant {
+                            name: "Blue",
+                            config: vec![EnumVariantConfig::ProtoFieldNumber(6)],
+                            docs: None,
+                            variant_type: EnumVariantType::Empty,
+                        },
+                    ],
+                }),
+            }],
+        };
+
+        let err = ProtobufCodegen::gen_declarations(&decls).unwrap_err();
+        assert!(err.to_string().contains("Color"));
+    }
+
+    #[test]
+    fn two_fields_pinned_to_the_same_number_is_a_reported_error() {
+        let decls = Declarations {
+            declarations: vec![TypeDeclaration {
+                name: "Point",
+                config: vec![],
+                docs: None,
+                value: DeclarationValue::TStruct(TStruct {
+                    fields: vec![
+                        StructField {
+                            name: "x",
+                            config: vec![StructFieldConfig::ProtoFieldNumber(1)],
+                            docs: None,
+                            field_type: StructFieldType::TPrimitive(TPrimitive::Ti64),
+                        },
+                        StructField {
+                            name: "y",
+                            config: vec![StructFieldConfig::ProtoFieldNumber(1)],
+                            docs: None,
+                            field_type: StructFieldType::TPrimitive(TPrimitive::Ti64),
+                        },
+                    ],
+                }),
+            }],
+        };
+
+        let err = ProtobufCodegen::gen_declarations(&decls).unwrap_err();
+        assert!(err.to_string().contains("x"));
+        assert!(err.to_string().contains("y"));
+    }
+
+    #[test]
+    fn field_pinned_to_zero_is_a_reported_error() {
+        let decls = Declarations {
+            declarations: vec![TypeDeclaration {
+                name: "Point",
+                config: vec![],
+                docs: None,
+                value: DeclarationValue::TStruct(TStruct {
+                    fields: vec![StructField {
+                        name: "x",
+                        config: vec![StructFieldConfig::ProtoFieldNumber(0)],
+                        docs: None,
+                        field_type: StructFieldType::TPrimitive(TPrimitive::Ti64),
+                    }],
+                }),
+            }],
+        };
+
+        let err = ProtobufCodegen::gen_declarations(&decls).unwrap_err();
+        assert!(err.to_string().contains("x"));
+    }
+}
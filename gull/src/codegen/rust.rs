@@ -1,23 +1,37 @@
 use super::docs::{format_docstring, CommentStyle};
-use super::Codegen;
+use super::{to_snake_case, Codegen};
 use crate::definitions::*;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use std::cell::RefCell;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 pub struct RustCodegen {
     imports: RefCell<BTreeSet<&'static str>>,
 }
 
+/// Opt-in knobs for [`RustCodegen`] that affect the whole generated file
+/// rather than a single declaration.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RustCodegenOptions {
+    /// Also emit a `Visit`/`VisitMut` traversal layer (see [`RustCodegen::gen_visitor`]).
+    pub generate_visitor: bool,
+}
+
 impl Codegen for RustCodegen {
     fn gen_declarations(declarations: &Declarations) -> Result<String> {
         let rc = RustCodegen::new();
 
+        let declared: HashMap<&str, &DeclarationValue> = declarations
+            .declarations
+            .iter()
+            .map(|d| (d.name, &d.value))
+            .collect();
+
         let mut declarations_code = String::new();
 
         for declaration in &declarations.declarations {
             declarations_code.push('\n');
-            declarations_code.push_str(&rc.gen_declaration(declaration)?);
+            declarations_code.push_str(&rc.gen_declaration(declaration, &declared)?);
             declarations_code.push('\n');
         }
 
@@ -35,24 +49,55 @@ impl Codegen for RustCodegen {
 }
 
 impl RustCodegen {
+    const DEFAULT_DERIVES: &'static [&'static str] = &["Debug", "serde::Serialize", "serde::Deserialize"];
+
     fn new() -> Self {
         Self {
             imports: RefCell::new(BTreeSet::new()),
         }
     }
 
+    /// Like [`Codegen::gen_declarations`], but additionally honours
+    /// [`RustCodegenOptions`] for output that isn't tied to a single
+    /// declaration.
+    pub fn gen_declarations_with_options(
+        declarations: &Declarations,
+        options: RustCodegenOptions,
+    ) -> Result<String> {
+        let mut result = Self::gen_declarations(declarations)?;
+
+        if options.generate_visitor {
+            let rc = RustCodegen::new();
+            result.push('\n');
+            result.push_str(&rc.gen_visitor(declarations));
+        }
+
+        Ok(result)
+    }
+
     fn add_import(&self, import: &'static str) {
         self.imports.borrow_mut().insert(import);
     }
 
-    fn gen_declaration(&self, declaration: &TypeDeclaration) -> Result<String> {
+    fn gen_declaration(
+        &self,
+        declaration: &TypeDeclaration,
+        declared: &HashMap<&str, &DeclarationValue>,
+    ) -> Result<String> {
         let mut prefix = String::new();
+        let mut generate_builder = false;
+        let mut derives: &[&'static str] = Self::DEFAULT_DERIVES;
+        let mut serde_tag = None;
         for config in &declaration.config {
             match config {
                 TypeDeclarationConfig::RustAttribute(attr) => {
                     prefix.push_str(attr);
                     prefix.push('\n')
                 }
+                // Handled below, once we know the declaration is a TStruct/TEnum.
+                TypeDeclarationConfig::GenerateBuilder => generate_builder = true,
+                TypeDeclarationConfig::Derives(d) => derives = d,
+                TypeDeclarationConfig::SerdeTag(tag) => serde_tag = Some(tag),
             }
         }
 
@@ -61,8 +106,7 @@ impl RustCodegen {
                 "pub type {} = {};",
                 declaration.name,
                 self.gen_primitive_type(p)
-            )
-            .into(),
+            ),
             DeclarationValue::TMap(m) => {
                 format!("pub type {} = {};", declaration.name, self.gen_map(m))
             }
@@ -70,10 +114,22 @@ impl RustCodegen {
                 format!("pub type {} = {};", declaration.name, self.gen_tuple(t))
             }
             DeclarationValue::TStruct(s) => {
-                prefix.push_str("#[derive(Debug, serde::Serialize, serde::Deserialize)]\n");
-                format!("pub struct {} {}", declaration.name, self.gen_struct(s, 0))
+                prefix.push_str(&self.gen_derive_attribute(derives));
+                let mut r = format!("pub struct {} {}", declaration.name, self.gen_struct(s, 0));
+                if generate_builder {
+                    r.push('\n');
+                    r.push_str(&self.gen_builder(declaration.name, s));
+                }
+                r
             }
             DeclarationValue::TEnum(e) => {
+                prefix.push_str(&self.gen_derive_attribute(derives));
+                if let Some(tag) = serde_tag {
+                    if self.derives_serde(derives) {
+                        self.validate_serde_tag(tag, e, declared)?;
+                        prefix.push_str(&self.gen_serde_tag_attribute(tag));
+                    }
+                }
                 format!("pub enum {} {}", declaration.name, self.gen_enum(e))
             }
             DeclarationValue::Docs => String::new(),
@@ -92,6 +148,83 @@ impl RustCodegen {
         Ok(format!("{}{}", prefix, r))
     }
 
+    fn gen_derive_attribute(&self, derives: &[&'static str]) -> String {
+        for derive in derives {
+            match *derive {
+                "Serialize" => self.add_import("use serde::Serialize;"),
+                "Deserialize" => self.add_import("use serde::Deserialize;"),
+                _ => {}
+            }
+        }
+
+        format!("#[derive({})]\n", derives.join(", "))
+    }
+
+    fn derives_serde(&self, derives: &[&'static str]) -> bool {
+        derives
+            .iter()
+            .any(|d| *d == "Serialize" || *d == "serde::Serialize" || *d == "serde::Deserialize" || *d == "Deserialize")
+    }
+
+    fn gen_serde_tag_attribute(&self, tag: &SerdeTag) -> String {
+        match tag {
+            SerdeTag::Internal(tag) => format!("#[serde(tag = \"{}\")]\n", tag),
+            SerdeTag::Adjacent { tag, content } => {
+                format!("#[serde(tag = \"{}\", content = \"{}\")]\n", tag, content)
+            }
+            SerdeTag::Untagged => "#[serde(untagged)]\n".to_string(),
+        }
+    }
+
+    /// Internally-tagged enums (`#[serde(tag = "...")]`) can only represent
+    /// variants that serde serializes as a map: serde_derive rejects
+    /// multi-item tuple variants outright, and a newtype variant wrapping a
+    /// non-map type compiles but panics at serialization time. Reject both
+    /// up front rather than emitting code that can't compile or can't run.
+    fn validate_serde_tag(
+        &self,
+        tag: &SerdeTag,
+        e: &TEnum,
+        declared: &HashMap<&str, &DeclarationValue>,
+    ) -> Result<()> {
+        let SerdeTag::Internal(tag_name) = tag else {
+            return Ok(());
+        };
+
+        for variant in &e.variants {
+            match &variant.variant_type {
+                EnumVariantType::Tuple(t) if t.items.len() > 1 => {
+                    return Err(anyhow!(
+                        "variant `{}` is a {}-item tuple, which can't be internally tagged (#[serde(tag = \"{}\")]); use SerdeTag::Adjacent or wrap the fields in a struct",
+                        variant.name,
+                        t.items.len(),
+                        tag_name
+                    ));
+                }
+                EnumVariantType::Tuple(t) if t.items.len() == 1 => {
+                    let serializes_as_map = match &t.items[0] {
+                        TupleItem::Reference(r) => matches!(
+                            declared.get(r.name),
+                            Some(DeclarationValue::TStruct(_)) | Some(DeclarationValue::TMap(_))
+                        ),
+                        TupleItem::TPrimitive(_) => false,
+                    };
+
+                    if !serializes_as_map {
+                        return Err(anyhow!(
+                            "variant `{}` wraps a value that doesn't serialize as a map, which can't be internally tagged (#[serde(tag = \"{}\")]); use SerdeTag::Adjacent or SerdeTag::Untagged instead",
+                            variant.name,
+                            tag_name
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
     fn gen_map(&self, m: &TMap) -> String {
         let value = match &m.value {
             TMapValue::TPrimitive(p) => self.gen_primitive_type(p),
@@ -123,7 +256,7 @@ impl RustCodegen {
     fn gen_option(&self, o: &TOption) -> String {
         let value = match &o {
             TOption::Reference(r) => r.name.into(),
-            TOption::TPrimitive(p) => self.gen_primitive_type(&p).into(),
+            TOption::TPrimitive(p) => self.gen_primitive_type(p).into(),
             TOption::TMap(m) => self.gen_map(m),
             TOption::TVec(v) => self.gen_vec(v),
             TOption::TSet(s) => self.gen_set(s),
@@ -131,6 +264,76 @@ impl RustCodegen {
         format!("Option<{}>", value)
     }
 
+    fn gen_field_type(&self, field_type: &StructFieldType) -> String {
+        match field_type {
+            StructFieldType::Reference(r) => r.name.into(),
+            StructFieldType::TMap(m) => self.gen_map(m),
+            StructFieldType::TOption(o) => self.gen_option(o),
+            StructFieldType::TPrimitive(p) => self.gen_primitive_type(p).into(),
+            StructFieldType::TTuple(t) => self.gen_tuple(t),
+            StructFieldType::TVec(v) => self.gen_vec(v),
+        }
+    }
+
+    /// Emits a `FooBuilder` with one `Option<T>` slot per field plus
+    /// `with_<field>` setters and a `build()`, and a `Foo::new(...)`
+    /// constructor taking the non-`TOption` fields positionally.
+    fn gen_builder(&self, name: &str, s: &TStruct) -> String {
+        let mut slots = String::new();
+        let mut setters = String::new();
+        let mut build_fields = String::new();
+        let mut new_params = Vec::new();
+        let mut new_with_calls = Vec::new();
+
+        for field in &s.fields {
+            let field_type = self.gen_field_type(&field.field_type);
+
+            slots.push_str(&format!("\n    {}: Option<{}>,", field.name, field_type));
+
+            setters.push_str(&format!(
+                "\n    pub fn with_{name}(mut self, {name}: {ty}) -> Self {{\n        self.{name} = Some({name});\n        self\n    }}\n",
+                name = field.name,
+                ty = field_type,
+            ));
+
+            if matches!(field.field_type, StructFieldType::TOption(_)) {
+                build_fields.push_str(&format!(
+                    "\n            {name}: self.{name}.unwrap_or(None),",
+                    name = field.name
+                ));
+                continue;
+            }
+
+            if matches!(
+                field.field_type,
+                StructFieldType::TVec(_) | StructFieldType::TMap(_)
+            ) {
+                build_fields.push_str(&format!(
+                    "\n            {name}: self.{name}.unwrap_or_default(),",
+                    name = field.name
+                ));
+            } else {
+                build_fields.push_str(&format!(
+                    "\n            {name}: self.{name}.ok_or_else(|| anyhow::anyhow!(\"missing required field `{name}`\"))?,",
+                    name = field.name
+                ));
+            }
+
+            new_params.push(format!("{}: {}", field.name, field_type));
+            new_with_calls.push(format!(".with_{name}({name})", name = field.name));
+        }
+
+        format!(
+            "impl {name} {{\n    pub fn new({params}) -> Self {{\n        {name}Builder::default(){with_calls}\n            .build()\n            .expect(\"all required fields were provided\")\n    }}\n}}\n\n#[derive(Default)]\npub struct {name}Builder {{{slots}\n}}\n\nimpl {name}Builder {{{setters}\n    pub fn build(self) -> anyhow::Result<{name}> {{\n        Ok({name} {{{build_fields}\n        }})\n    }}\n}}\n",
+            name = name,
+            params = new_params.join(", "),
+            with_calls = new_with_calls.join(""),
+            slots = slots,
+            setters = setters,
+            build_fields = build_fields,
+        )
+    }
+
     fn gen_struct(&self, s: &TStruct, indent_level: usize) -> String {
         let mut fields = String::new();
 
@@ -144,17 +347,12 @@ impl RustCodegen {
                     StructFieldConfig::RustAttribute(attr) => {
                         field_prefix.push_str(&format!("\n    {}{}", indent, attr))
                     }
+                    // Interpreted by other codegen backends (e.g. ProtobufCodegen, SqlCodegen).
+                    StructFieldConfig::ProtoFieldNumber(_) | StructFieldConfig::SqlPrimaryKey => {}
                 }
             }
 
-            let field_type = match &field.field_type {
-                StructFieldType::Reference(r) => r.name.into(),
-                StructFieldType::TMap(m) => self.gen_map(m),
-                StructFieldType::TOption(o) => self.gen_option(o),
-                StructFieldType::TPrimitive(p) => self.gen_primitive_type(&p).into(),
-                StructFieldType::TTuple(t) => self.gen_tuple(t),
-                StructFieldType::TVec(v) => self.gen_vec(v),
-            };
+            let field_type = self.gen_field_type(&field.field_type);
 
             let mut field_str = format!("\n    {}{}: {},", &indent, field.name, field_type);
 
@@ -219,4 +417,648 @@ impl RustCodegen {
             TPrimitive::Tf64 => "f64",
         }
     }
+
+    /// Emits a `Visit`/`VisitMut` trait plus free `walk_*`/`walk_mut_*`
+    /// functions so consumers can traverse a whole tree of generated types
+    /// without hand-writing the recursion themselves.
+    fn gen_visitor(&self, declarations: &Declarations) -> String {
+        let names: BTreeSet<&str> = declarations
+            .declarations
+            .iter()
+            .map(|d| d.name)
+            .collect();
+
+        let nodes: Vec<&TypeDeclaration> = declarations
+            .declarations
+            .iter()
+            .filter(|d| {
+                matches!(
+                    d.value,
+                    DeclarationValue::TStruct(_) | DeclarationValue::TEnum(_)
+                )
+            })
+            .collect();
+
+        let mut result = String::new();
+        result.push_str(&self.gen_visit_trait("Visit", false, &nodes));
+        result.push('\n');
+        for node in &nodes {
+            result.push_str(&self.gen_walk_fn(node, &names, false));
+        }
+        result.push('\n');
+        result.push_str(&self.gen_visit_trait("VisitMut", true, &nodes));
+        result.push('\n');
+        for node in &nodes {
+            result.push_str(&self.gen_walk_fn(node, &names, true));
+        }
+
+        result
+    }
+
+    fn gen_visit_trait(&self, trait_name: &str, mutable: bool, nodes: &[&TypeDeclaration]) -> String {
+        let reference = if mutable { "&mut " } else { "&" };
+        let walk_prefix = if mutable { "walk_mut_" } else { "walk_" };
+
+        let mut methods = String::new();
+        for node in nodes {
+            let snake = to_snake_case(node.name);
+            methods.push_str(&format!(
+                "    fn visit_{snake}(&mut self, node: {reference}{name}) {{\n        {walk_prefix}{snake}(self, node)\n    }}\n",
+                snake = snake,
+                reference = reference,
+                name = node.name,
+                walk_prefix = walk_prefix,
+            ));
+        }
+
+        format!("pub trait {} {{\n{}}}\n", trait_name, methods)
+    }
+
+    fn gen_walk_fn(&self, node: &TypeDeclaration, names: &BTreeSet<&str>, mutable: bool) -> String {
+        let snake = to_snake_case(node.name);
+        let fn_name = if mutable {
+            format!("walk_mut_{}", snake)
+        } else {
+            format!("walk_{}", snake)
+        };
+        let trait_name = if mutable { "VisitMut" } else { "Visit" };
+        let reference = if mutable { "&mut " } else { "&" };
+
+        let (body, uses_node) = match &node.value {
+            DeclarationValue::TStruct(s) => {
+                let mut body = String::new();
+                for field in &s.fields {
+                    let expr = format!("node.{}", field.name);
+                    body.push_str(&self.gen_walk_field(&field.field_type, &expr, names, mutable, false));
+                }
+                let uses_node = !body.is_empty();
+                (body, uses_node)
+            }
+            DeclarationValue::TEnum(e) => {
+                let mut arms = String::new();
+                for variant in &e.variants {
+                    arms.push_str(&self.gen_walk_variant_arm(node.name, variant, names, mutable));
+                }
+                // The match scrutinee always reads `node`, even when no arm
+                // recurses further.
+                (format!("    match node {{\n{}    }}\n", arms), true)
+            }
+            _ => (String::new(), false),
+        };
+
+        // `visitor`/`node` go unused when a type has no reference-typed
+        // fields (or variants) to recurse into; keep the generated output
+        // warning-free rather than relying on every caller enabling the
+        // `unused_variables` allowance.
+        let uses_visitor = body.contains("visitor.");
+        let neutralize = match (uses_visitor, uses_node) {
+            (true, true) => "",
+            (false, true) => "    let _ = visitor;\n",
+            (true, false) => "    let _ = node;\n",
+            (false, false) => "    let _ = (visitor, node);\n",
+        };
+        let body = format!("{}{}", neutralize, body);
+
+        format!(
+            "pub fn {fn_name}<V: {trait_name} + ?Sized>(visitor: &mut V, node: {reference}{name}) {{\n{body}}}\n",
+            fn_name = fn_name,
+            trait_name = trait_name,
+            reference = reference,
+            name = node.name,
+            body = body,
+        )
+    }
+
+    fn gen_walk_field(
+        &self,
+        field_type: &StructFieldType,
+        expr: &str,
+        names: &BTreeSet<&str>,
+        mutable: bool,
+        expr_is_ref: bool,
+    ) -> String {
+        match field_type {
+            StructFieldType::Reference(r) => {
+                self.visit_call_if_known(r.name, expr, names, mutable, expr_is_ref)
+            }
+            StructFieldType::TVec(v) => self.gen_walk_vec(v, expr, names, mutable),
+            StructFieldType::TMap(m) => self.gen_walk_map(m, expr, names, mutable),
+            StructFieldType::TTuple(t) => self.gen_walk_tuple(t, expr, names, mutable),
+            StructFieldType::TOption(o) => self.gen_walk_option(o, expr, names, mutable, expr_is_ref),
+            StructFieldType::TPrimitive(_) => String::new(),
+        }
+    }
+
+    /// `expr_is_ref` is true when `expr` already denotes a `&T`/`&mut T`
+    /// (e.g. an enum variant binding under match ergonomics); in that case
+    /// no reference is added, avoiding a redundant `&(*expr)`.
+    fn visit_call_if_known(
+        &self,
+        name: &str,
+        expr: &str,
+        names: &BTreeSet<&str>,
+        mutable: bool,
+        expr_is_ref: bool,
+    ) -> String {
+        if !names.contains(name) {
+            return String::new();
+        }
+        let reference = if expr_is_ref {
+            ""
+        } else if mutable {
+            "&mut "
+        } else {
+            "&"
+        };
+        format!(
+            "    visitor.visit_{}({}{});\n",
+            to_snake_case(name),
+            reference,
+            expr
+        )
+    }
+
+    fn gen_walk_vec(&self, v: &TVec, expr: &str, names: &BTreeSet<&str>, mutable: bool) -> String {
+        let TVec::Reference(r) = v else {
+            return String::new();
+        };
+        if !names.contains(r.name) {
+            return String::new();
+        }
+
+        if mutable {
+            format!(
+                "    for item in {}.iter_mut() {{\n        visitor.visit_{}(item);\n    }}\n",
+                expr,
+                to_snake_case(r.name)
+            )
+        } else {
+            format!(
+                "    for item in {}.iter() {{\n        visitor.visit_{}(item);\n    }}\n",
+                expr,
+                to_snake_case(r.name)
+            )
+        }
+    }
+
+    fn gen_walk_map(&self, m: &TMap, expr: &str, names: &BTreeSet<&str>, mutable: bool) -> String {
+        let TMapValue::Reference(r) = &m.value else {
+            return String::new();
+        };
+        if !names.contains(r.name) {
+            return String::new();
+        }
+
+        if mutable {
+            format!(
+                "    for item in {}.values_mut() {{\n        visitor.visit_{}(item);\n    }}\n",
+                expr,
+                to_snake_case(r.name)
+            )
+        } else {
+            format!(
+                "    for item in {}.values() {{\n        visitor.visit_{}(item);\n    }}\n",
+                expr,
+                to_snake_case(r.name)
+            )
+        }
+    }
+
+    fn gen_walk_tuple(&self, t: &TTuple, expr: &str, names: &BTreeSet<&str>, mutable: bool) -> String {
+        let mut body = String::new();
+        for (i, item) in t.items.iter().enumerate() {
+            if let TupleItem::Reference(r) = item {
+                let item_expr = format!("{}.{}", expr, i);
+                body.push_str(&self.visit_call_if_known(r.name, &item_expr, names, mutable, false));
+            }
+        }
+        body
+    }
+
+    fn gen_walk_option(
+        &self,
+        o: &TOption,
+        expr: &str,
+        names: &BTreeSet<&str>,
+        mutable: bool,
+        expr_is_ref: bool,
+    ) -> String {
+        let amp = if expr_is_ref {
+            ""
+        } else if mutable {
+            "&mut "
+        } else {
+            "&"
+        };
+        let iter_method = if mutable { "iter_mut" } else { "iter" };
+        let values_method = if mutable { "values_mut" } else { "values" };
+
+        match o {
+            TOption::Reference(r) if names.contains(r.name) => format!(
+                "    if let Some(inner) = {}{} {{\n        visitor.visit_{}(inner);\n    }}\n",
+                amp,
+                expr,
+                to_snake_case(r.name)
+            ),
+            TOption::TVec(TVec::Reference(r)) if names.contains(r.name) => format!(
+                "    if let Some(items) = {}{} {{\n        for item in items.{}() {{\n            visitor.visit_{}(item);\n        }}\n    }}\n",
+                amp,
+                expr,
+                iter_method,
+                to_snake_case(r.name)
+            ),
+            TOption::TSet(TSet::Reference(r)) if names.contains(r.name) && !mutable => format!(
+                "    if let Some(items) = {}{} {{\n        for item in items.{}() {{\n            visitor.visit_{}(item);\n        }}\n    }}\n",
+                amp,
+                expr,
+                iter_method,
+                to_snake_case(r.name)
+            ),
+            TOption::TMap(m) => match &m.value {
+                TMapValue::Reference(r) if names.contains(r.name) => format!(
+                    "    if let Some(map) = {}{} {{\n        for item in map.{}() {{\n            visitor.visit_{}(item);\n        }}\n    }}\n",
+                    amp,
+                    expr,
+                    values_method,
+                    to_snake_case(r.name)
+                ),
+                _ => String::new(),
+            },
+            _ => String::new(),
+        }
+    }
+
+    fn gen_walk_variant_arm(
+        &self,
+        enum_name: &str,
+        variant: &EnumVariant,
+        names: &BTreeSet<&str>,
+        mutable: bool,
+    ) -> String {
+        match &variant.variant_type {
+            EnumVariantType::Empty => format!("        {}::{} => {{}}\n", enum_name, variant.name),
+            EnumVariantType::Tuple(t) => {
+                let mut bindings = Vec::with_capacity(t.items.len());
+                let mut body = String::new();
+                for (i, item) in t.items.iter().enumerate() {
+                    let call = if let TupleItem::Reference(r) = item {
+                        self.visit_call_if_known(r.name, &format!("v{}", i), names, mutable, true)
+                    } else {
+                        String::new()
+                    };
+                    // Bind `_` instead of an unused `v{i}` when this position
+                    // isn't recursed into, so the arm stays warning-free.
+                    bindings.push(if call.is_empty() {
+                        "_".to_string()
+                    } else {
+                        format!("v{}", i)
+                    });
+                    body.push_str(&call);
+                }
+                format!(
+                    "        {}::{}({}) => {{\n{}        }}\n",
+                    enum_name,
+                    variant.name,
+                    bindings.join(", "),
+                    body
+                )
+            }
+            EnumVariantType::Struct(s) => {
+                let mut field_names = Vec::with_capacity(s.fields.len());
+                let mut body = String::new();
+                for field in &s.fields {
+                    let field_body =
+                        self.gen_walk_field(&field.field_type, field.name, names, mutable, true);
+                    field_names.push(if field_body.is_empty() {
+                        format!("{}: _", field.name)
+                    } else {
+                        field.name.to_string()
+                    });
+                    body.push_str(&field_body);
+                }
+                format!(
+                    "        {}::{} {{ {} }} => {{\n{}        }}\n",
+                    enum_name,
+                    variant.name,
+                    field_names.join(", "),
+                    body
+                )
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf_and_shape() -> Declarations {
+        Declarations {
+            declarations: vec![
+                TypeDeclaration {
+                    name: "Tag",
+                    config: vec![],
+                    docs: None,
+                    value: DeclarationValue::TStruct(TStruct {
+                        fields: vec![StructField {
+                            name: "value",
+                            config: vec![],
+                            docs: None,
+                            field_type: StructFieldType::TPrimitive(TPrimitive::String),
+                        }],
+                    }),
+                },
+                TypeDeclaration {
+                    name: "Point",
+                    config: vec![],
+                    docs: None,
+                    value: DeclarationValue::TStruct(TStruct {
+                        fields: vec![StructField {
+                            name: "x",
+                            config: vec![],
+                            docs: None,
+                            field_type: StructFieldType::TPrimitive(TPrimitive::Ti64),
+                        }],
+                    }),
+                },
+                TypeDeclaration {
+                    name: "Shape",
+                    config: vec![],
+                    docs: None,
+                    value: DeclarationValue::TEnum(TEnum {
+                        variants: vec![
+                            EnumVariant {
+                                name: "Single",
+                                config: vec![],
+                                docs: None,
+                                variant_type: EnumVariantType::Tuple(TTuple {
+                                    items: vec![TupleItem::Reference(Reference { name: "Point" })],
+                                }),
+                            },
+                            EnumVariant {
+                                name: "Pair",
+                                config: vec![],
+                                docs: None,
+                                variant_type: EnumVariantType::Struct(TStruct {
+                                    fields: vec![StructField {
+                                        name: "a",
+                                        config: vec![],
+                                        docs: None,
+                                        field_type: StructFieldType::Reference(Reference { name: "Point" }),
+                                    }],
+                                }),
+                            },
+                        ],
+                    }),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn leaf_struct_walk_fn_has_no_unused_params() {
+        let decls = leaf_and_shape();
+        let code = RustCodegen::gen_declarations_with_options(
+            &decls,
+            RustCodegenOptions { generate_visitor: true },
+        )
+        .unwrap();
+
+        assert!(
+            code.contains("pub fn walk_tag<V: Visit + ?Sized>(visitor: &mut V, node: &Tag) {\n    let _ = (visitor, node);\n}"),
+            "leaf struct's walk fn should neutralize its unused params:\n{code}"
+        );
+    }
+
+    #[test]
+    fn variant_arm_bindings_are_passed_without_rederef() {
+        let decls = leaf_and_shape();
+        let code = RustCodegen::gen_declarations_with_options(
+            &decls,
+            RustCodegenOptions { generate_visitor: true },
+        )
+        .unwrap();
+
+        assert!(
+            code.contains("visitor.visit_point(v0);"),
+            "tuple variant binding should be passed directly, not `&(*v0)`:\n{code}"
+        );
+        assert!(
+            code.contains("visitor.visit_point(a);"),
+            "struct variant binding should be passed directly, not `&(*a)`:\n{code}"
+        );
+        assert!(!code.contains("(*v0)") && !code.contains("(*a)"));
+    }
+
+    #[test]
+    fn leaf_enum_neutralizes_unused_visitor() {
+        let decls = Declarations {
+            declarations: vec![TypeDeclaration {
+                name: "Color",
+                config: vec![],
+                docs: None,
+                value: DeclarationValue::TEnum(TEnum {
+                    variants: vec![
+                        EnumVariant {
+                            name: "Red",
+                            config: vec![],
+                            docs: None,
+                            variant_type: EnumVariantType::Empty,
+                        },
+                        EnumVariant {
+                            name: "Named",
+                            config: vec![],
+                            docs: None,
+                            variant_type: EnumVariantType::Tuple(TTuple {
+                                items: vec![TupleItem::TPrimitive(TPrimitive::String)],
+                            }),
+                        },
+                    ],
+                }),
+            }],
+        };
+
+        let code = RustCodegen::gen_declarations_with_options(
+            &decls,
+            RustCodegenOptions { generate_visitor: true },
+        )
+        .unwrap();
+
+        assert!(
+            code.contains("pub fn walk_color<V: Visit + ?Sized>(visitor: &mut V, node: &Color) {\n    let _ = visitor;\n"),
+            "an enum with no reference-typed variants should neutralize the unused visitor param:\n{code}"
+        );
+        assert!(
+            code.contains("Color::Named(_)"),
+            "a tuple variant with no reference items should bind `_`, not an unused `v0`:\n{code}"
+        );
+    }
+
+    #[test]
+    fn mixed_variant_fields_bind_unused_positions_as_underscore() {
+        let decls = Declarations {
+            declarations: vec![
+                TypeDeclaration {
+                    name: "Point",
+                    config: vec![],
+                    docs: None,
+                    value: DeclarationValue::TStruct(TStruct {
+                        fields: vec![StructField {
+                            name: "x",
+                            config: vec![],
+                            docs: None,
+                            field_type: StructFieldType::TPrimitive(TPrimitive::Ti64),
+                        }],
+                    }),
+                },
+                TypeDeclaration {
+                    name: "Shape",
+                    config: vec![],
+                    docs: None,
+                    value: DeclarationValue::TEnum(TEnum {
+                        variants: vec![
+                            EnumVariant {
+                                name: "CircleTuple",
+                                config: vec![],
+                                docs: None,
+                                variant_type: EnumVariantType::Tuple(TTuple {
+                                    items: vec![
+                                        TupleItem::Reference(Reference { name: "Point" }),
+                                        TupleItem::TPrimitive(TPrimitive::Tf64),
+                                    ],
+                                }),
+                            },
+                            EnumVariant {
+                                name: "CircleStruct",
+                                config: vec![],
+                                docs: None,
+                                variant_type: EnumVariantType::Struct(TStruct {
+                                    fields: vec![
+                                        StructField {
+                                            name: "center",
+                                            config: vec![],
+                                            docs: None,
+                                            field_type: StructFieldType::Reference(Reference { name: "Point" }),
+                                        },
+                                        StructField {
+                                            name: "radius",
+                                            config: vec![],
+                                            docs: None,
+                                            field_type: StructFieldType::TPrimitive(TPrimitive::Tf64),
+                                        },
+                                    ],
+                                }),
+                            },
+                        ],
+                    }),
+                },
+            ],
+        };
+
+        let code = RustCodegen::gen_declarations_with_options(
+            &decls,
+            RustCodegenOptions { generate_visitor: true },
+        )
+        .unwrap();
+
+        assert!(
+            code.contains("Shape::CircleTuple(v0, _)"),
+            "the primitive tuple position should bind `_`, not an unused `v1`:\n{code}"
+        );
+        assert!(
+            code.contains("Shape::CircleStruct { center, radius: _ }"),
+            "the primitive struct field should bind `_`, not an unused `radius`:\n{code}"
+        );
+    }
+
+    #[test]
+    fn internally_tagged_multi_item_tuple_variant_is_a_reported_error() {
+        let decls = Declarations {
+            declarations: vec![TypeDeclaration {
+                name: "Shape",
+                config: vec![TypeDeclarationConfig::SerdeTag(SerdeTag::Internal("type"))],
+                docs: None,
+                value: DeclarationValue::TEnum(TEnum {
+                    variants: vec![EnumVariant {
+                        name: "Circle",
+                        config: vec![],
+                        docs: None,
+                        variant_type: EnumVariantType::Tuple(TTuple {
+                            items: vec![
+                                TupleItem::TPrimitive(TPrimitive::Tf64),
+                                TupleItem::TPrimitive(TPrimitive::Tf64),
+                            ],
+                        }),
+                    }],
+                }),
+            }],
+        };
+
+        let err = RustCodegen::gen_declarations(&decls).unwrap_err();
+        assert!(err.to_string().contains("Circle"));
+    }
+
+    #[test]
+    fn internally_tagged_scalar_newtype_variant_is_a_reported_error() {
+        let decls = Declarations {
+            declarations: vec![TypeDeclaration {
+                name: "Shape",
+                config: vec![TypeDeclarationConfig::SerdeTag(SerdeTag::Internal("type"))],
+                docs: None,
+                value: DeclarationValue::TEnum(TEnum {
+                    variants: vec![EnumVariant {
+                        name: "Named",
+                        config: vec![],
+                        docs: None,
+                        variant_type: EnumVariantType::Tuple(TTuple {
+                            items: vec![TupleItem::TPrimitive(TPrimitive::String)],
+                        }),
+                    }],
+                }),
+            }],
+        };
+
+        let err = RustCodegen::gen_declarations(&decls).unwrap_err();
+        assert!(err.to_string().contains("Named"));
+    }
+
+    #[test]
+    fn internally_tagged_newtype_wrapping_a_struct_is_allowed() {
+        let decls = Declarations {
+            declarations: vec![
+                TypeDeclaration {
+                    name: "Point",
+                    config: vec![],
+                    docs: None,
+                    value: DeclarationValue::TStruct(TStruct {
+                        fields: vec![StructField {
+                            name: "x",
+                            config: vec![],
+                            docs: None,
+                            field_type: StructFieldType::TPrimitive(TPrimitive::Ti64),
+                        }],
+                    }),
+                },
+                TypeDeclaration {
+                    name: "Shape",
+                    config: vec![TypeDeclarationConfig::SerdeTag(SerdeTag::Internal("type"))],
+                    docs: None,
+                    value: DeclarationValue::TEnum(TEnum {
+                        variants: vec![EnumVariant {
+                            name: "Circle",
+                            config: vec![],
+                            docs: None,
+                            variant_type: EnumVariantType::Tuple(TTuple {
+                                items: vec![TupleItem::Reference(Reference { name: "Point" })],
+                            }),
+                        }],
+                    }),
+                },
+            ],
+        };
+
+        let code = RustCodegen::gen_declarations(&decls).unwrap();
+        assert!(code.contains("#[serde(tag = \"type\")]"));
+    }
 }
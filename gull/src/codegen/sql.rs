@@ -0,0 +1,354 @@
+use super::{to_snake_case, Codegen};
+use crate::definitions::*;
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+/// Projects `TStruct` declarations onto `CREATE TABLE` statements, so the
+/// same IR that drives the Rust types can provision storage.
+///
+/// A `TVec`/`TSet`/`TMap` field is lowered into its own child table carrying
+/// a foreign key back to the parent row, the same relational expansion
+/// model-generation crates use when flattening nested records to SQL.
+/// `TEnum`/`TTuple`/`TPrimitive`/`TMap` declarations have no direct SQL
+/// representation and are skipped. Fields with no column representation
+/// (e.g. `TTuple`) surface as an `anyhow::Error`, matching the convention
+/// [`crate::codegen::protobuf::ProtobufCodegen`] established for its own
+/// unsupported constructs.
+pub struct SqlCodegen;
+
+impl Codegen for SqlCodegen {
+    fn gen_declarations(declarations: &Declarations) -> Result<String> {
+        let sql = SqlCodegen;
+
+        let pk_columns: HashMap<&str, &str> = declarations
+            .declarations
+            .iter()
+            .filter_map(|d| match &d.value {
+                DeclarationValue::TStruct(s) => Some((d.name, sql.primary_key_column(s))),
+                _ => None,
+            })
+            .collect();
+
+        let mut result = String::new();
+        for declaration in &declarations.declarations {
+            if let DeclarationValue::TStruct(s) = &declaration.value {
+                result.push_str(&sql.gen_tables(declaration.name, s, &pk_columns)?);
+                result.push('\n');
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl SqlCodegen {
+    /// The column name a referencing table's foreign key must point at: the
+    /// field designated via [`StructFieldConfig::SqlPrimaryKey`], or `id`
+    /// for the default `BIGSERIAL PRIMARY KEY`.
+    fn primary_key_column<'a>(&self, s: &'a TStruct) -> &'a str {
+        s.fields
+            .iter()
+            .find(|f| {
+                f.config
+                    .iter()
+                    .any(|c| matches!(c, StructFieldConfig::SqlPrimaryKey))
+            })
+            .map(|f| f.name)
+            .unwrap_or("id")
+    }
+
+    /// Emits the table for `s` itself, plus one child table per
+    /// collection-typed field.
+    fn gen_tables(
+        &self,
+        name: &str,
+        s: &TStruct,
+        pk_columns: &HashMap<&str, &str>,
+    ) -> Result<String> {
+        let table = to_snake_case(name);
+        let primary_key = s.fields.iter().find(|f| {
+            f.config
+                .iter()
+                .any(|c| matches!(c, StructFieldConfig::SqlPrimaryKey))
+        });
+
+        let mut columns = Vec::new();
+        columns.push(match primary_key {
+            Some(f) => {
+                let sql_type = self.gen_column_type(&f.field_type).map_err(|_| {
+                    anyhow!(
+                        "primary key field `{}` must be a scalar column type",
+                        f.name
+                    )
+                })?;
+                format!("  {} {} PRIMARY KEY", f.name, sql_type)
+            }
+            None => "  id BIGSERIAL PRIMARY KEY".to_string(),
+        });
+
+        let mut child_tables = String::new();
+
+        for field in &s.fields {
+            if primary_key.is_some_and(|pk| std::ptr::eq(pk, field)) {
+                continue;
+            }
+
+            match &field.field_type {
+                StructFieldType::Reference(r) => {
+                    columns.push(format!(
+                        "  {col}_id BIGINT NOT NULL REFERENCES {ref_table}({ref_pk})",
+                        col = field.name,
+                        ref_table = to_snake_case(r.name),
+                        ref_pk = pk_column_for(pk_columns, r.name),
+                    ));
+                }
+                StructFieldType::TOption(TOption::Reference(r)) => {
+                    columns.push(format!(
+                        "  {col}_id BIGINT REFERENCES {ref_table}({ref_pk})",
+                        col = field.name,
+                        ref_table = to_snake_case(r.name),
+                        ref_pk = pk_column_for(pk_columns, r.name),
+                    ));
+                }
+                StructFieldType::TVec(_) | StructFieldType::TMap(_) => {
+                    child_tables.push_str(&self.gen_child_table(
+                        &table,
+                        self.primary_key_column(s),
+                        field,
+                        pk_columns,
+                    )?);
+                }
+                StructFieldType::TOption(TOption::TVec(_) | TOption::TSet(_) | TOption::TMap(_)) => {
+                    child_tables.push_str(&self.gen_child_table(
+                        &table,
+                        self.primary_key_column(s),
+                        field,
+                        pk_columns,
+                    )?);
+                }
+                _ => {
+                    let nullable = matches!(field.field_type, StructFieldType::TOption(_));
+                    let sql_type = self.gen_column_type(&field.field_type).map_err(|_| {
+                        anyhow!(
+                            "SQL backend has no column type for tuple-typed field `{}`",
+                            field.name
+                        )
+                    })?;
+                    let not_null = if nullable { "" } else { " NOT NULL" };
+                    columns.push(format!("  {} {}{}", field.name, sql_type, not_null));
+                }
+            }
+        }
+
+        Ok(format!(
+            "CREATE TABLE {table} (\n{columns}\n);\n{child_tables}",
+            table = table,
+            columns = columns.join(",\n"),
+            child_tables = child_tables,
+        ))
+    }
+
+    /// A `TVec`/`TSet`/`TMap` field becomes its own table, owned by the
+    /// parent row via a `<parent>_id` foreign key referencing the parent's
+    /// actual primary key column (`parent_pk`).
+    fn gen_child_table(
+        &self,
+        parent_table: &str,
+        parent_pk: &str,
+        field: &StructField,
+        pk_columns: &HashMap<&str, &str>,
+    ) -> Result<String> {
+        let child_table = format!("{}_{}", parent_table, field.name);
+        let parent_fk = format!("{}_id", parent_table);
+
+        let value_column = match &field.field_type {
+            StructFieldType::TVec(v) => {
+                self.gen_collection_value_column(&CollectionValue::Vec(v), pk_columns)
+            }
+            StructFieldType::TMap(m) => {
+                self.gen_collection_value_column(&CollectionValue::Map(m), pk_columns)
+            }
+            StructFieldType::TOption(TOption::TVec(v)) => {
+                self.gen_collection_value_column(&CollectionValue::Vec(v), pk_columns)
+            }
+            StructFieldType::TOption(TOption::TSet(s)) => {
+                self.gen_collection_value_column(&CollectionValue::Set(s), pk_columns)
+            }
+            StructFieldType::TOption(TOption::TMap(m)) => {
+                self.gen_collection_value_column(&CollectionValue::Map(m), pk_columns)
+            }
+            other => {
+                return Err(anyhow!(
+                    "gen_child_table called on non-collection field `{}` of type {:?}",
+                    field.name,
+                    other
+                ))
+            }
+        };
+
+        Ok(format!(
+            "CREATE TABLE {child_table} (\n  id BIGSERIAL PRIMARY KEY,\n  {parent_fk} BIGINT NOT NULL REFERENCES {parent_table}({parent_pk}),\n{value_column}\n);\n",
+            child_table = child_table,
+            parent_fk = parent_fk,
+            parent_table = parent_table,
+            parent_pk = parent_pk,
+            value_column = value_column,
+        ))
+    }
+
+    fn gen_collection_value_column(
+        &self,
+        value: &CollectionValue,
+        pk_columns: &HashMap<&str, &str>,
+    ) -> String {
+        match value {
+            CollectionValue::Vec(TVec::TPrimitive(p)) | CollectionValue::Set(TSet::TPrimitive(p)) => {
+                format!("  value {} NOT NULL", self.gen_primitive_type(p))
+            }
+            CollectionValue::Vec(TVec::Reference(r)) | CollectionValue::Set(TSet::Reference(r)) => {
+                format!(
+                    "  value_id BIGINT NOT NULL REFERENCES {}({})",
+                    to_snake_case(r.name),
+                    pk_column_for(pk_columns, r.name),
+                )
+            }
+            CollectionValue::Map(m) => {
+                let value_column = match &m.value {
+                    TMapValue::TPrimitive(p) => format!("value {} NOT NULL", self.gen_primitive_type(p)),
+                    TMapValue::Reference(r) => format!(
+                        "value_id BIGINT NOT NULL REFERENCES {}({})",
+                        to_snake_case(r.name),
+                        pk_column_for(pk_columns, r.name),
+                    ),
+                };
+                format!(
+                    "  key {} NOT NULL,\n  {}",
+                    self.gen_primitive_type(&m.key),
+                    value_column
+                )
+            }
+        }
+    }
+
+    /// `Err` for any field type with no scalar column representation (e.g. `TTuple`).
+    fn gen_column_type(&self, field_type: &StructFieldType) -> Result<String> {
+        match field_type {
+            StructFieldType::TPrimitive(p) => Ok(self.gen_primitive_type(p).to_string()),
+            StructFieldType::TOption(TOption::TPrimitive(p)) => Ok(self.gen_primitive_type(p).to_string()),
+            other => Err(anyhow!("no scalar SQL column type for {:?}", other)),
+        }
+    }
+
+    fn gen_primitive_type(&self, ty: &TPrimitive) -> &'static str {
+        match ty {
+            TPrimitive::String => "TEXT",
+            TPrimitive::Tbool => "BOOLEAN",
+            TPrimitive::Ti64 => "BIGINT",
+            TPrimitive::Tf64 => "DOUBLE PRECISION",
+        }
+    }
+}
+
+/// Looks up the primary key column of the table a FK references, falling
+/// back to `id` if `ref_name` isn't a known struct declaration (e.g. it was
+/// only referenced, not defined, in this `Declarations`).
+fn pk_column_for<'a>(pk_columns: &HashMap<&str, &'a str>, ref_name: &str) -> &'a str {
+    pk_columns.get(ref_name).copied().unwrap_or("id")
+}
+
+enum CollectionValue<'a> {
+    Vec(&'a TVec),
+    Set(&'a TSet),
+    Map(&'a TMap),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tuple_typed_field_is_a_reported_error() {
+        let decls = Declarations {
+            declarations: vec![TypeDeclaration {
+                name: "Weird",
+                config: vec![],
+                docs: None,
+                value: DeclarationValue::TStruct(TStruct {
+                    fields: vec![StructField {
+                        name: "pair",
+                        config: vec![],
+                        docs: None,
+                        field_type: StructFieldType::TTuple(TTuple {
+                            items: vec![TupleItem::TPrimitive(TPrimitive::Ti64)],
+                        }),
+                    }],
+                }),
+            }],
+        };
+
+        let err = SqlCodegen::gen_declarations(&decls).unwrap_err();
+        assert!(err.to_string().contains("pair"));
+    }
+
+    #[test]
+    fn non_scalar_primary_key_is_a_reported_error() {
+        let decls = Declarations {
+            declarations: vec![TypeDeclaration {
+                name: "Child",
+                config: vec![],
+                docs: None,
+                value: DeclarationValue::TStruct(TStruct {
+                    fields: vec![StructField {
+                        name: "parent",
+                        config: vec![StructFieldConfig::SqlPrimaryKey],
+                        docs: None,
+                        field_type: StructFieldType::Reference(Reference { name: "Parent" }),
+                    }],
+                }),
+            }],
+        };
+
+        let err = SqlCodegen::gen_declarations(&decls).unwrap_err();
+        assert!(err.to_string().contains("parent"));
+    }
+
+    #[test]
+    fn foreign_keys_reference_the_target_s_actual_primary_key_column() {
+        let decls = Declarations {
+            declarations: vec![
+                TypeDeclaration {
+                    name: "Parent",
+                    config: vec![],
+                    docs: None,
+                    value: DeclarationValue::TStruct(TStruct {
+                        fields: vec![StructField {
+                            name: "uuid",
+                            config: vec![StructFieldConfig::SqlPrimaryKey],
+                            docs: None,
+                            field_type: StructFieldType::TPrimitive(TPrimitive::String),
+                        }],
+                    }),
+                },
+                TypeDeclaration {
+                    name: "Child",
+                    config: vec![],
+                    docs: None,
+                    value: DeclarationValue::TStruct(TStruct {
+                        fields: vec![StructField {
+                            name: "parent",
+                            config: vec![],
+                            docs: None,
+                            field_type: StructFieldType::Reference(Reference { name: "Parent" }),
+                        }],
+                    }),
+                },
+            ],
+        };
+
+        let sql = SqlCodegen::gen_declarations(&decls).unwrap();
+        assert!(
+            sql.contains("REFERENCES parent(uuid)"),
+            "FK should reference the parent's actual primary key column, not a hardcoded `id`:\n{sql}"
+        );
+    }
+}
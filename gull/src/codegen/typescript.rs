@@ -0,0 +1,186 @@
+use super::docs::{format_docstring, CommentStyle};
+use super::Codegen;
+use crate::definitions::*;
+use anyhow::Result;
+
+/// Projects the [`Declarations`] IR onto TypeScript type definitions,
+/// mirroring [`super::rust::RustCodegen`] one field at a time.
+///
+/// Unlike `RustCodegen`, there's no Rust-specific `use` prelude to collect,
+/// so this backend simply has no import state of its own.
+pub struct TypeScriptCodegen;
+
+impl Codegen for TypeScriptCodegen {
+    fn gen_declarations(declarations: &Declarations) -> Result<String> {
+        let ts = TypeScriptCodegen;
+
+        let mut result = String::new();
+
+        for declaration in &declarations.declarations {
+            result.push('\n');
+            result.push_str(&ts.gen_declaration(declaration)?);
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+}
+
+impl TypeScriptCodegen {
+    fn gen_declaration(&self, declaration: &TypeDeclaration) -> Result<String> {
+        let mut r = match &declaration.value {
+            DeclarationValue::TPrimitive(p) => format!(
+                "export type {} = {};",
+                declaration.name,
+                self.gen_primitive_type(p)
+            ),
+            DeclarationValue::TMap(m) => {
+                format!("export type {} = {};", declaration.name, self.gen_map(m))
+            }
+            DeclarationValue::TTuple(t) => {
+                format!("export type {} = {};", declaration.name, self.gen_tuple(t))
+            }
+            DeclarationValue::TStruct(s) => format!(
+                "export interface {} {}",
+                declaration.name,
+                self.gen_struct(s)
+            ),
+            DeclarationValue::TEnum(e) => format!(
+                "export type {} = {};",
+                declaration.name,
+                self.gen_enum(e)
+            ),
+            DeclarationValue::Docs => String::new(),
+        };
+
+        if let Some(doc) = format_docstring(declaration.docs, CommentStyle::JsDoc, 0) {
+            r = format!("{}{}", doc, r);
+        }
+
+        Ok(r)
+    }
+
+    fn gen_map(&self, m: &TMap) -> String {
+        let value = match &m.value {
+            TMapValue::TPrimitive(p) => self.gen_primitive_type(p).to_string(),
+            TMapValue::Reference(d) => d.name.to_string(),
+        };
+
+        match m.key {
+            TPrimitive::String => format!("Record<string, {}>", value),
+            _ => format!("Map<{}, {}>", self.gen_primitive_type(&m.key), value),
+        }
+    }
+
+    fn gen_vec(&self, v: &TVec) -> String {
+        let value = match &v {
+            TVec::TPrimitive(p) => self.gen_primitive_type(p),
+            TVec::Reference(d) => d.name,
+        };
+        format!("Array<{}>", value)
+    }
+
+    fn gen_set(&self, s: &TSet) -> String {
+        let value = match &s {
+            TSet::TPrimitive(p) => self.gen_primitive_type(p),
+            TSet::Reference(d) => d.name,
+        };
+        format!("Set<{}>", value)
+    }
+
+    fn gen_option(&self, o: &TOption) -> String {
+        let value = match &o {
+            TOption::Reference(r) => r.name.to_string(),
+            TOption::TPrimitive(p) => self.gen_primitive_type(p).to_string(),
+            TOption::TMap(m) => self.gen_map(m),
+            TOption::TVec(v) => self.gen_vec(v),
+            TOption::TSet(s) => self.gen_set(s),
+        };
+        format!("{} | null", value)
+    }
+
+    fn gen_struct(&self, s: &TStruct) -> String {
+        let mut fields = String::new();
+
+        for field in s.fields.iter() {
+            let field_type = self.gen_struct_field_type(&field.field_type);
+
+            let mut field_str = format!("\n    {}: {};", field.name, field_type);
+
+            if let Some(doc) = format_docstring(field.docs, CommentStyle::JsDoc, 4) {
+                field_str = format!("\n{}{}", doc, field_str);
+            }
+
+            fields.push_str(&field_str);
+        }
+
+        format!("{{{}\n}}", fields)
+    }
+
+    fn gen_struct_field_type(&self, field_type: &StructFieldType) -> String {
+        match field_type {
+            StructFieldType::Reference(r) => r.name.to_string(),
+            StructFieldType::TMap(m) => self.gen_map(m),
+            StructFieldType::TOption(o) => self.gen_option(o),
+            StructFieldType::TPrimitive(p) => self.gen_primitive_type(p).to_string(),
+            StructFieldType::TTuple(t) => self.gen_tuple(t),
+            StructFieldType::TVec(v) => self.gen_vec(v),
+        }
+    }
+
+    /// Renders a `TEnum` as a TypeScript discriminated union: one object
+    /// variant per member, tagged by a `type` field.
+    fn gen_enum(&self, e: &TEnum) -> String {
+        let mut variants = Vec::new();
+
+        for variant in &e.variants {
+            let tag = format!("type: \"{}\"", variant.name);
+
+            let variant_str = match &variant.variant_type {
+                EnumVariantType::Empty => format!("{{ {} }}", tag),
+                EnumVariantType::Tuple(t) => {
+                    format!("{{ {}; value: {} }}", tag, self.gen_tuple(t))
+                }
+                EnumVariantType::Struct(s) => {
+                    let fields = self.gen_struct_fields_inline(s);
+                    format!("{{ {}{} }}", tag, fields)
+                }
+            };
+
+            variants.push(variant_str);
+        }
+
+        variants.join(" | ")
+    }
+
+    fn gen_struct_fields_inline(&self, s: &TStruct) -> String {
+        let mut fields = String::new();
+        for field in s.fields.iter() {
+            let field_type = self.gen_struct_field_type(&field.field_type);
+            fields.push_str(&format!("; {}: {}", field.name, field_type));
+        }
+        fields
+    }
+
+    fn gen_tuple(&self, t: &TTuple) -> String {
+        let mut values = Vec::new();
+
+        for item in &t.items {
+            values.push(match item {
+                TupleItem::Reference(d) => d.name.to_string(),
+                TupleItem::TPrimitive(p) => self.gen_primitive_type(p).to_string(),
+            });
+        }
+
+        format!("[{}]", values.join(", "))
+    }
+
+    fn gen_primitive_type(&self, ty: &TPrimitive) -> &'static str {
+        match ty {
+            TPrimitive::String => "string",
+            TPrimitive::Tbool => "boolean",
+            TPrimitive::Ti64 => "number",
+            TPrimitive::Tf64 => "number",
+        }
+    }
+}
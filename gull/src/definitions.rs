@@ -0,0 +1,186 @@
+//! The intermediate representation that every codegen backend consumes.
+//!
+//! A [`Declarations`] is a flat list of named types (structs, enums, type
+//! aliases) built up by hand or by a macro, then projected onto a target
+//! language by a [`crate::codegen::Codegen`] implementation.
+
+/// A reference to another declared type, resolved by name.
+#[derive(Debug, Clone, Copy)]
+pub struct Reference {
+    pub name: &'static str,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TPrimitive {
+    String,
+    Tbool,
+    Ti64,
+    Tf64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TMap {
+    pub key: TPrimitive,
+    pub value: TMapValue,
+}
+
+#[derive(Debug, Clone)]
+pub enum TMapValue {
+    TPrimitive(TPrimitive),
+    Reference(Reference),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TVec {
+    TPrimitive(TPrimitive),
+    Reference(Reference),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TSet {
+    TPrimitive(TPrimitive),
+    Reference(Reference),
+}
+
+#[derive(Debug, Clone)]
+pub enum TOption {
+    Reference(Reference),
+    TPrimitive(TPrimitive),
+    TMap(TMap),
+    TVec(TVec),
+    TSet(TSet),
+}
+
+#[derive(Debug, Clone)]
+pub struct TTuple {
+    pub items: Vec<TupleItem>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TupleItem {
+    Reference(Reference),
+    TPrimitive(TPrimitive),
+}
+
+#[derive(Debug, Clone)]
+pub struct TStruct {
+    pub fields: Vec<StructField>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StructField {
+    pub name: &'static str,
+    pub config: Vec<StructFieldConfig>,
+    pub field_type: StructFieldType,
+    pub docs: Option<&'static str>,
+}
+
+/// Per-field knobs that a codegen backend may interpret. Backends ignore
+/// configs they don't understand.
+#[derive(Debug, Clone)]
+pub enum StructFieldConfig {
+    /// Emitted verbatim above the field by [`crate::codegen::rust::RustCodegen`].
+    RustAttribute(&'static str),
+    /// Pins the field's proto tag number for [`crate::codegen::protobuf::ProtobufCodegen`]
+    /// instead of letting it auto-assign one.
+    ProtoFieldNumber(u32),
+    /// Marks this field as the table's primary key for [`crate::codegen::sql::SqlCodegen`],
+    /// instead of the auto-generated `id BIGSERIAL` column.
+    SqlPrimaryKey,
+}
+
+#[derive(Debug, Clone)]
+pub enum StructFieldType {
+    Reference(Reference),
+    TMap(TMap),
+    TOption(TOption),
+    TPrimitive(TPrimitive),
+    TTuple(TTuple),
+    TVec(TVec),
+}
+
+#[derive(Debug, Clone)]
+pub struct TEnum {
+    pub variants: Vec<EnumVariant>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumVariant {
+    pub name: &'static str,
+    pub config: Vec<EnumVariantConfig>,
+    pub variant_type: EnumVariantType,
+    pub docs: Option<&'static str>,
+}
+
+#[derive(Debug, Clone)]
+pub enum EnumVariantType {
+    Empty,
+    Tuple(TTuple),
+    Struct(TStruct),
+}
+
+/// Per-variant knobs that a codegen backend may interpret. Backends ignore
+/// configs they don't understand.
+#[derive(Debug, Clone)]
+pub enum EnumVariantConfig {
+    /// Pins the variant's proto tag number for [`crate::codegen::protobuf::ProtobufCodegen`]
+    /// instead of letting it auto-assign one.
+    ProtoFieldNumber(u32),
+}
+
+#[derive(Debug, Clone)]
+pub enum DeclarationValue {
+    TPrimitive(TPrimitive),
+    TMap(TMap),
+    TTuple(TTuple),
+    TStruct(TStruct),
+    TEnum(TEnum),
+    /// A free-floating doc comment with no associated type.
+    Docs,
+}
+
+/// Per-declaration knobs that a codegen backend may interpret. Backends
+/// ignore configs they don't understand.
+#[derive(Debug, Clone)]
+pub enum TypeDeclarationConfig {
+    /// Emitted verbatim above the declaration by [`crate::codegen::rust::RustCodegen`].
+    RustAttribute(&'static str),
+    /// Also emit a typed builder (`FooBuilder`) and a positional `Foo::new`
+    /// constructor for this `TStruct`, via [`crate::codegen::rust::RustCodegen`].
+    GenerateBuilder,
+    /// Overrides the `#[derive(...)]` list [`crate::codegen::rust::RustCodegen`]
+    /// emits above this declaration. Defaults to
+    /// `["Debug", "serde::Serialize", "serde::Deserialize"]` when absent.
+    Derives(Vec<&'static str>),
+    /// Tags a `TEnum`'s serde representation. Only emitted when the
+    /// effective derive list includes `serde::Serialize`/`serde::Deserialize`.
+    SerdeTag(SerdeTag),
+}
+
+/// How a `TEnum`'s serde representation is tagged on the wire. See
+/// <https://serde.rs/enum-representations.html>.
+#[derive(Debug, Clone)]
+pub enum SerdeTag {
+    /// `#[serde(tag = "...")]`
+    Internal(&'static str),
+    /// `#[serde(tag = "...", content = "...")]`
+    Adjacent {
+        tag: &'static str,
+        content: &'static str,
+    },
+    /// `#[serde(untagged)]`
+    Untagged,
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeDeclaration {
+    pub name: &'static str,
+    pub config: Vec<TypeDeclarationConfig>,
+    pub value: DeclarationValue,
+    pub docs: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Declarations {
+    pub declarations: Vec<TypeDeclaration>,
+}